@@ -0,0 +1,88 @@
+use rand::Rng;
+
+use crate::vec3::Vec3;
+
+/// Picks a random point inside the unit disk via rejection sampling, for
+/// jittering lens-origin samples in `Camera::get_ray`.
+fn random_in_unit_disk() -> (f64, f64) {
+    let mut rng = rand::thread_rng();
+    loop {
+        let rx = rng.gen_range(-1.0..1.0);
+        let ry = rng.gen_range(-1.0..1.0);
+        if rx * rx + ry * ry < 1.0 {
+            return (rx, ry);
+        }
+    }
+}
+
+/// A positionable camera: an eye at `look_from` aimed at `look_at`, with
+/// `vup` fixing the roll and `vfov_degrees` the vertical field of view.
+/// `aperture` and `focus_dist` add thin-lens depth of field; pass
+/// `aperture: 0.0` for a pinhole camera with everything in focus.
+#[derive(Debug)]
+pub struct Camera {
+    look_from: Vec3,
+    half_width: f64,
+    half_height: f64,
+    lens_radius: f64,
+    focus_dist: f64,
+    u: Vec3,
+    v: Vec3,
+    w: Vec3,
+}
+
+impl Camera {
+    pub fn new(
+        look_from: Vec3,
+        look_at: Vec3,
+        vup: Vec3,
+        vfov_degrees: f64,
+        aspect_ratio: f64,
+        aperture: f64,
+        focus_dist: f64,
+    ) -> Self {
+        let half_height = (vfov_degrees.to_radians() / 2.0).tan();
+        let half_width = aspect_ratio * half_height;
+
+        let w = look_from.sub(&look_at).normalize();
+        let u = vup.cross(&w).normalize();
+        let v = w.cross(&u);
+
+        Self {
+            look_from,
+            half_width,
+            half_height,
+            lens_radius: aperture / 2.0,
+            focus_dist,
+            u,
+            v,
+            w,
+        }
+    }
+
+    /// Maps a normalized screen coordinate `(s, t)` in `[-1, 1]` to a
+    /// world-space ray. When `lens_radius` is non-zero the ray origin is
+    /// jittered over the lens disk and aimed at the same point on the focal
+    /// plane, producing depth-of-field blur away from that plane.
+    pub fn get_ray(&self, s: f64, t: f64) -> (Vec3, Vec3) {
+        let dir = self
+            .w
+            .mul(-1.0)
+            .add(&self.u.mul(s * self.half_width))
+            .add(&self.v.mul(t * self.half_height))
+            .normalize();
+
+        if self.lens_radius <= 0.0 {
+            return (self.look_from, dir);
+        }
+
+        let (rx, ry) = random_in_unit_disk();
+        let lens_offset = self
+            .u
+            .mul(rx * self.lens_radius)
+            .add(&self.v.mul(ry * self.lens_radius));
+        let orig = self.look_from.add(&lens_offset);
+        let target = self.look_from.add(&dir.mul(self.focus_dist));
+        (orig, target.sub(&orig).normalize())
+    }
+}
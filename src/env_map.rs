@@ -0,0 +1,130 @@
+use std::f64::consts::PI;
+use std::fs;
+use std::io;
+
+use crate::vec3::Vec3;
+
+/// A flat background color, or an equirectangular environment image
+/// sampled by escaping ray direction.
+pub enum Background {
+    Flat(Vec3),
+    Map(EnvMap),
+}
+
+impl Background {
+    pub fn sample(&self, dir: &Vec3) -> Vec3 {
+        match self {
+            Background::Flat(color) => *color,
+            Background::Map(map) => map.sample(dir),
+        }
+    }
+}
+
+/// An equirectangular (lat-long) background image loaded from a binary
+/// (P6) PPM file.
+pub struct EnvMap {
+    width: usize,
+    height: usize,
+    pixels: Vec<Vec3>,
+}
+
+impl EnvMap {
+    pub fn load(path: &str) -> io::Result<Self> {
+        let bytes = fs::read(path)?;
+        let mut cursor = 0;
+
+        let mut next_token = || -> io::Result<String> {
+            while cursor < bytes.len() && bytes[cursor].is_ascii_whitespace() {
+                cursor += 1;
+            }
+            let start = cursor;
+            while cursor < bytes.len() && !bytes[cursor].is_ascii_whitespace() {
+                cursor += 1;
+            }
+            Ok(String::from_utf8_lossy(&bytes[start..cursor]).into_owned())
+        };
+
+        let magic = next_token()?;
+        if magic != "P6" {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("unsupported PPM magic `{magic}`, expected P6"),
+            ));
+        }
+        let width: usize = next_token()?
+            .parse()
+            .map_err(|_| io::Error::new(io::ErrorKind::InvalidData, "invalid PPM width"))?;
+        let height: usize = next_token()?
+            .parse()
+            .map_err(|_| io::Error::new(io::ErrorKind::InvalidData, "invalid PPM height"))?;
+        let maxval: usize = next_token()?
+            .parse()
+            .map_err(|_| io::Error::new(io::ErrorKind::InvalidData, "invalid PPM maxval"))?;
+
+        // single whitespace byte separates the header from the binary data
+        cursor += 1;
+
+        let data = &bytes[cursor..];
+        let expected_bytes = width
+            .checked_mul(height)
+            .and_then(|pixels| pixels.checked_mul(3))
+            .ok_or_else(|| {
+                io::Error::new(io::ErrorKind::InvalidData, "PPM dimensions overflow usize")
+            })?;
+        if data.len() < expected_bytes {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!(
+                    "truncated PPM: expected {expected_bytes} bytes of pixel data, got {}",
+                    data.len()
+                ),
+            ));
+        }
+
+        let mut pixels = Vec::with_capacity(width * height);
+        for chunk in data.chunks_exact(3).take(width * height) {
+            pixels.push(Vec3 {
+                x: chunk[0] as f64 / maxval as f64,
+                y: chunk[1] as f64 / maxval as f64,
+                z: chunk[2] as f64 / maxval as f64,
+            });
+        }
+
+        Ok(Self {
+            width,
+            height,
+            pixels,
+        })
+    }
+
+    fn at(&self, x: usize, y: usize) -> Vec3 {
+        self.pixels[y.min(self.height - 1) * self.width + x.min(self.width - 1)]
+    }
+
+    /// Samples the map by ray direction, converting to spherical
+    /// coordinates and bilinearly filtering the four nearest texels.
+    pub fn sample(&self, dir: &Vec3) -> Vec3 {
+        let u = dir.z.atan2(dir.x) / (2.0 * PI) + 0.5;
+        let v = dir.y.clamp(-1.0, 1.0).acos() / PI;
+
+        let fx = u * self.width as f64 - 0.5;
+        let fy = v * self.height as f64 - 0.5;
+        let x0 = fx.floor();
+        let y0 = fy.floor();
+        let tx = fx - x0;
+        let ty = fy - y0;
+
+        let wrap = |x: f64| -> usize {
+            let w = self.width as i64;
+            (((x as i64 % w) + w) % w) as usize
+        };
+        let x0 = wrap(x0);
+        let x1 = wrap(x0 as f64 + 1.0);
+        let y0 = (y0.max(0.0) as usize).min(self.height - 1);
+        let y1 = (y0 + 1).min(self.height - 1);
+
+        let top = self.at(x0, y0).mul(1.0 - tx).add(&self.at(x1, y0).mul(tx));
+        let bottom = self.at(x0, y1).mul(1.0 - tx).add(&self.at(x1, y1).mul(tx));
+        top.mul(1.0 - ty).add(&bottom.mul(ty))
+    }
+}
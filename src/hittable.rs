@@ -0,0 +1,77 @@
+use crate::material::Material;
+use crate::vec3::Vec3;
+
+/// Result of a successful `Hittable::ray_intersect`: how far along the ray
+/// the hit occurred, where it occurred, the surface normal there, and the
+/// material to shade it with.
+pub struct Hit {
+    pub distance: f64,
+    pub point: Vec3,
+    pub normal: Vec3,
+    pub material: Material,
+}
+
+/// Anything that a ray can intersect. Implementing this is all a new
+/// primitive needs to be rendered; `scene_intersect` and `cast_ray` never
+/// need to know the concrete type. `Sync` is required so a `Vec<Box<dyn
+/// Hittable>>` can be shared across the rayon-parallelized pixel loop.
+pub trait Hittable: Sync {
+    fn ray_intersect(&self, orig: &Vec3, dir: &Vec3) -> Option<Hit>;
+}
+
+#[derive(Debug)]
+pub struct Sphere {
+    pub center: Vec3,
+    pub radius: f64,
+    pub material: Material,
+}
+
+impl Hittable for Sphere {
+    fn ray_intersect(&self, orig: &Vec3, dir: &Vec3) -> Option<Hit> {
+        let l = self.center.sub(orig);
+        let tca = l.dot(dir);
+        let d2 = l.dot(&l) - tca * tca;
+        let r2 = self.radius * self.radius;
+        if d2 > r2 {
+            return None;
+        }
+
+        let thc = (r2 - d2).sqrt();
+        let t0 = tca - thc;
+        let t1 = tca + thc;
+
+        let distance = if t0 >= 0.0 {
+            t0
+        } else if t1 >= 0.0 {
+            t1
+        } else {
+            return None;
+        };
+
+        let point = orig.add(&dir.mul(distance));
+        let normal = point.sub(&self.center).normalize();
+        Some(Hit {
+            distance,
+            point,
+            normal,
+            material: self.material,
+        })
+    }
+}
+
+pub fn scene_intersect(orig: &Vec3, dir: &Vec3, objects: &[Box<dyn Hittable>]) -> Option<Hit> {
+    let mut closest_hit = None;
+    for object in objects {
+        if let Some(hit) = object.ray_intersect(orig, dir) {
+            match &closest_hit {
+                None => closest_hit = Some(hit),
+                Some(old_hit) => {
+                    if hit.distance < old_hit.distance {
+                        closest_hit = Some(hit)
+                    }
+                }
+            }
+        }
+    }
+    closest_hit
+}
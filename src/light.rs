@@ -0,0 +1,7 @@
+use crate::vec3::Vec3;
+
+#[derive(Debug)]
+pub struct Light {
+    pub position: Vec3,
+    pub intensity: f64,
+}
@@ -0,0 +1,24 @@
+use crate::vec3::Vec3;
+
+#[derive(Debug, Clone, Copy)]
+pub struct Material {
+    // color of material
+    pub diffuse_color: Vec3,
+
+    // albedo[0] - diffuse reflection constant
+    // albedo[1] - specular reflection constant
+    // albedo[2] - reflectance ?
+    // albedo[3] - refractance ?
+    pub albedo: [f64; 4],
+
+    // shininess constant
+    pub specular_exponent: f64,
+
+    // ?
+    pub refractive_index: f64,
+
+    // when true, the reflect/refract split (normally albedo[2]/albedo[3])
+    // is instead computed per-hit via Schlick's approximation, so grazing
+    // angles reflect more and glancing-through angles refract more
+    pub fresnel: bool,
+}
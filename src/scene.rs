@@ -0,0 +1,238 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+
+use crate::camera::Camera;
+use crate::env_map::{Background, EnvMap};
+use crate::hittable::{Hittable, Sphere};
+use crate::light::Light;
+use crate::material::Material;
+use crate::triangle;
+use crate::vec3::Vec3;
+
+/// Everything needed to render a frame, loaded from a scene description
+/// file: the background, the geometry, the lights, and the camera.
+pub struct Scene {
+    pub background: Background,
+    pub objects: Vec<Box<dyn Hittable>>,
+    pub lights: Vec<Light>,
+    pub camera: Camera,
+}
+
+#[derive(Debug)]
+pub enum SceneError {
+    Io(std::io::Error),
+    Parse(String),
+}
+
+impl fmt::Display for SceneError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SceneError::Io(err) => write!(f, "failed to read scene file: {err}"),
+            SceneError::Parse(msg) => write!(f, "failed to parse scene file: {msg}"),
+        }
+    }
+}
+
+impl std::error::Error for SceneError {}
+
+impl From<std::io::Error> for SceneError {
+    fn from(err: std::io::Error) -> Self {
+        SceneError::Io(err)
+    }
+}
+
+fn parse_f64(tokens: &mut std::str::SplitWhitespace<'_>, directive: &str) -> Result<f64, SceneError> {
+    tokens
+        .next()
+        .ok_or_else(|| SceneError::Parse(format!("{directive}: missing number")))?
+        .parse()
+        .map_err(|_| SceneError::Parse(format!("{directive}: invalid number")))
+}
+
+fn parse_vec3(tokens: &mut std::str::SplitWhitespace<'_>, directive: &str) -> Result<Vec3, SceneError> {
+    Ok(Vec3 {
+        x: parse_f64(tokens, directive)?,
+        y: parse_f64(tokens, directive)?,
+        z: parse_f64(tokens, directive)?,
+    })
+}
+
+/// Parses a scene description from `path`. Supported directives, one per
+/// line (blank lines and `#`-prefixed comments are ignored):
+///
+/// ```text
+/// camera <fov> <lookfrom x y z> <lookat x y z> [<aperture> <focus_dist>]
+/// bkgcolor <r g b>
+/// bkgmap <ppm-path>
+/// light <x y z> <intensity>
+/// material <name> <diffuse rgb> <albedo0..3> <spec_exp> <refr_idx> <fresnel 0|1>
+/// sphere <x y z> <radius> <material-name>
+/// mesh <obj-path> <material-name>
+/// ```
+///
+/// `aperture`/`focus_dist` add thin-lens depth of field and default to a
+/// pinhole camera (`aperture: 0.0`) focused on `lookat` when omitted.
+///
+/// `fresnel` selects how a material's reflect/refract split is computed:
+/// `0` uses the fixed `albedo[2]`/`albedo[3]` weights, `1` computes them
+/// per-hit via Schlick's approximation.
+///
+/// `bkgmap` loads a binary (P6) PPM as an equirectangular environment map.
+/// `bkgcolor` and `bkgmap` both set the background, so whichever of them
+/// appears last in the file wins.
+///
+/// Materials must be declared before any `sphere` directive that names them.
+pub fn load(path: &str, aspect_ratio: f64) -> Result<Scene, SceneError> {
+    let contents = fs::read_to_string(path)?;
+
+    let mut background = Background::Flat(Vec3 {
+        x: 0.2,
+        y: 0.7,
+        z: 0.8,
+    });
+    let mut camera = None;
+    let mut materials: HashMap<String, Material> = HashMap::new();
+    let mut objects: Vec<Box<dyn Hittable>> = Vec::new();
+    let mut lights = Vec::new();
+
+    for line in contents.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let mut tokens = line.split_whitespace();
+        let directive = tokens
+            .next()
+            .ok_or_else(|| SceneError::Parse("empty directive".to_string()))?;
+
+        match directive {
+            "bkgcolor" => {
+                background = Background::Flat(parse_vec3(&mut tokens, "bkgcolor")?);
+            }
+            "bkgmap" => {
+                let map_path = tokens
+                    .next()
+                    .ok_or_else(|| SceneError::Parse("bkgmap: missing path".to_string()))?;
+                let env_map = EnvMap::load(map_path)
+                    .map_err(|err| SceneError::Parse(format!("bkgmap: {err}")))?;
+                background = Background::Map(env_map);
+            }
+            "camera" => {
+                let fov = parse_f64(&mut tokens, "camera")?;
+                let look_from = parse_vec3(&mut tokens, "camera")?;
+                let look_at = parse_vec3(&mut tokens, "camera")?;
+                // trailing <aperture> <focus_dist> are optional; default to
+                // a pinhole camera focused on look_at
+                let (aperture, focus_dist) = match (tokens.next(), tokens.next()) {
+                    (Some(aperture), Some(focus_dist)) => (
+                        aperture
+                            .parse()
+                            .map_err(|_| SceneError::Parse("camera: invalid aperture".to_string()))?,
+                        focus_dist.parse().map_err(|_| {
+                            SceneError::Parse("camera: invalid focus_dist".to_string())
+                        })?,
+                    ),
+                    (None, None) => (0.0, look_from.sub(&look_at).norm()),
+                    _ => {
+                        return Err(SceneError::Parse(
+                            "camera: aperture and focus_dist must both be given, or neither"
+                                .to_string(),
+                        ))
+                    }
+                };
+                camera = Some(Camera::new(
+                    look_from,
+                    look_at,
+                    Vec3 {
+                        x: 0.0,
+                        y: 1.0,
+                        z: 0.0,
+                    },
+                    fov,
+                    aspect_ratio,
+                    aperture,
+                    focus_dist,
+                ));
+            }
+            "light" => {
+                let position = parse_vec3(&mut tokens, "light")?;
+                let intensity = parse_f64(&mut tokens, "light")?;
+                lights.push(Light {
+                    position,
+                    intensity,
+                });
+            }
+            "material" => {
+                let name = tokens
+                    .next()
+                    .ok_or_else(|| SceneError::Parse("material: missing name".to_string()))?
+                    .to_string();
+                let diffuse_color = parse_vec3(&mut tokens, "material")?;
+                let albedo = [
+                    parse_f64(&mut tokens, "material")?,
+                    parse_f64(&mut tokens, "material")?,
+                    parse_f64(&mut tokens, "material")?,
+                    parse_f64(&mut tokens, "material")?,
+                ];
+                let specular_exponent = parse_f64(&mut tokens, "material")?;
+                let refractive_index = parse_f64(&mut tokens, "material")?;
+                let fresnel = parse_f64(&mut tokens, "material")? != 0.0;
+                materials.insert(
+                    name,
+                    Material {
+                        diffuse_color,
+                        albedo,
+                        specular_exponent,
+                        refractive_index,
+                        fresnel,
+                    },
+                );
+            }
+            "sphere" => {
+                let center = parse_vec3(&mut tokens, "sphere")?;
+                let radius = parse_f64(&mut tokens, "sphere")?;
+                let material_name = tokens
+                    .next()
+                    .ok_or_else(|| SceneError::Parse("sphere: missing material name".to_string()))?;
+                let material = *materials.get(material_name).ok_or_else(|| {
+                    SceneError::Parse(format!("sphere: unknown material `{material_name}`"))
+                })?;
+                objects.push(Box::new(Sphere {
+                    center,
+                    radius,
+                    material,
+                }));
+            }
+            "mesh" => {
+                let obj_path = tokens
+                    .next()
+                    .ok_or_else(|| SceneError::Parse("mesh: missing path".to_string()))?;
+                let material_name = tokens
+                    .next()
+                    .ok_or_else(|| SceneError::Parse("mesh: missing material name".to_string()))?;
+                let material = *materials.get(material_name).ok_or_else(|| {
+                    SceneError::Parse(format!("mesh: unknown material `{material_name}`"))
+                })?;
+                let mesh_triangles = triangle::load_obj(obj_path, material)
+                    .map_err(|err| SceneError::Parse(format!("mesh: {err}")))?;
+                objects.extend(
+                    mesh_triangles
+                        .into_iter()
+                        .map(|t| Box::new(t) as Box<dyn Hittable>),
+                );
+            }
+            other => return Err(SceneError::Parse(format!("unknown directive `{other}`"))),
+        }
+    }
+
+    let camera = camera.ok_or_else(|| SceneError::Parse("missing camera directive".to_string()))?;
+
+    Ok(Scene {
+        background,
+        objects,
+        lights,
+        camera,
+    })
+}
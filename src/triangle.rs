@@ -0,0 +1,118 @@
+use std::fs;
+
+use crate::hittable::{Hit, Hittable};
+use crate::material::Material;
+use crate::vec3::Vec3;
+
+const EPS: f64 = 1e-6;
+
+#[derive(Debug, Clone, Copy)]
+pub struct Triangle {
+    pub v0: Vec3,
+    pub v1: Vec3,
+    pub v2: Vec3,
+    pub material: Material,
+}
+
+impl Hittable for Triangle {
+    // Moller-Trumbore ray-triangle intersection.
+    fn ray_intersect(&self, orig: &Vec3, dir: &Vec3) -> Option<Hit> {
+        let e1 = self.v1.sub(&self.v0);
+        let e2 = self.v2.sub(&self.v0);
+        let pvec = dir.cross(&e2);
+        let det = e1.dot(&pvec);
+        if det.abs() < EPS {
+            // ray is parallel to the triangle
+            return None;
+        }
+        let inv = 1.0 / det;
+
+        let tvec = orig.sub(&self.v0);
+        let u = tvec.dot(&pvec) * inv;
+        if !(0.0..=1.0).contains(&u) {
+            return None;
+        }
+
+        let qvec = tvec.cross(&e1);
+        let v = dir.dot(&qvec) * inv;
+        if v < 0.0 || u + v > 1.0 {
+            return None;
+        }
+
+        let distance = e2.dot(&qvec) * inv;
+        if distance < EPS {
+            return None;
+        }
+
+        Some(Hit {
+            distance,
+            point: orig.add(&dir.mul(distance)),
+            normal: e1.cross(&e2).normalize(),
+            material: self.material,
+        })
+    }
+}
+
+/// Loads a triangle mesh from a Wavefront `.obj` file, parsing `v x y z`
+/// vertex lines and `f a b c ...` face lines (trailing `/vt`/`/vn` indices
+/// are ignored). Faces are triangulated as a fan if they have more than
+/// three vertices, and every triangle shares `material`.
+pub fn load_obj(path: &str, material: Material) -> std::io::Result<Vec<Triangle>> {
+    let contents = fs::read_to_string(path)?;
+
+    let mut vertices = Vec::new();
+    let mut faces = Vec::new();
+
+    for line in contents.lines() {
+        let mut tokens = line.split_whitespace();
+        match tokens.next() {
+            Some("v") => {
+                let coords: Vec<f64> = tokens.filter_map(|t| t.parse().ok()).collect();
+                if let [x, y, z] = coords[..] {
+                    vertices.push(Vec3 { x, y, z });
+                }
+            }
+            Some("f") => {
+                let mut indices = Vec::new();
+                for raw in tokens.filter_map(|t| t.split('/').next()) {
+                    let one_based: usize = raw.parse().map_err(|_| {
+                        std::io::Error::new(
+                            std::io::ErrorKind::InvalidData,
+                            format!("face references an invalid vertex index `{raw}`"),
+                        )
+                    })?;
+                    let zero_based = one_based.checked_sub(1).ok_or_else(|| {
+                        std::io::Error::new(
+                            std::io::ErrorKind::InvalidData,
+                            "face references vertex index 0, but OBJ indices are 1-based",
+                        )
+                    })?;
+                    indices.push(zero_based);
+                }
+                faces.push(indices);
+            }
+            _ => {}
+        }
+    }
+
+    let mut triangles = Vec::new();
+    for face in &faces {
+        if !face.iter().all(|&i| i < vertices.len()) {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!("face references a vertex index out of range: {face:?}"),
+            ));
+        }
+        // fan-triangulate faces with more than three vertices
+        for i in 1..face.len().saturating_sub(1) {
+            triangles.push(Triangle {
+                v0: vertices[face[0]],
+                v1: vertices[face[i]],
+                v2: vertices[face[i + 1]],
+                material,
+            });
+        }
+    }
+
+    Ok(triangles)
+}
@@ -0,0 +1,107 @@
+#[derive(Debug, Clone, Copy)]
+pub struct Pixel {
+    pub r: f64,
+    pub g: f64,
+    pub b: f64,
+}
+
+impl Pixel {
+    pub fn to_vec3(self) -> Vec3 {
+        Vec3 {
+            x: self.r,
+            y: self.g,
+            z: self.b,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Vec3 {
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+}
+
+impl Vec3 {
+    pub fn dot(&self, other: &Self) -> f64 {
+        self.x * other.x + self.y * other.y + self.z * other.z
+    }
+
+    pub fn add(&self, other: &Self) -> Self {
+        Self {
+            x: self.x + other.x,
+            y: self.y + other.y,
+            z: self.z + other.z,
+        }
+    }
+
+    pub fn sub(&self, other: &Self) -> Self {
+        Self {
+            x: self.x - other.x,
+            y: self.y - other.y,
+            z: self.z - other.z,
+        }
+    }
+
+    pub fn mul(&self, val: f64) -> Self {
+        Self {
+            x: self.x * val,
+            y: self.y * val,
+            z: self.z * val,
+        }
+    }
+
+    pub fn norm(&self) -> f64 {
+        self.dot(self).sqrt()
+    }
+
+    pub fn normalize(&self) -> Self {
+        self.mul(1.0 / self.norm())
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.x == 0.0 && self.y == 0.0 && self.z == 0.0
+    }
+
+    pub fn cross(&self, other: &Self) -> Self {
+        Self {
+            x: self.y * other.z - self.z * other.y,
+            y: self.z * other.x - self.x * other.z,
+            z: self.x * other.y - self.y * other.x,
+        }
+    }
+
+    pub fn to_pixel(self) -> Pixel {
+        Pixel {
+            r: self.x,
+            g: self.y,
+            b: self.z,
+        }
+    }
+
+    pub fn reflect(&self, normal: &Self) -> Self {
+        self.sub(&normal.mul(2.0 * self.dot(normal)))
+    }
+
+    pub fn refract(&self, mut normal: Self, refractive_index: f64) -> Self {
+        let mut cosi = -self.dot(&normal).clamp(-1.0, 1.0);
+        let eta;
+        if cosi < 0.0 {
+            cosi = -cosi;
+            normal = normal.mul(-1.0);
+            eta = refractive_index;
+        } else {
+            eta = 1.0 / refractive_index;
+        }
+        let k = 1.0 - eta.powi(2) * (1.0 - cosi.powi(2));
+        if k < 0.0 {
+            Self {
+                x: 0.0,
+                y: 0.0,
+                z: 0.0,
+            }
+        } else {
+            self.mul(eta).add(&normal.mul(eta * cosi - k.sqrt()))
+        }
+    }
+}